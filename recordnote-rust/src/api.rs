@@ -0,0 +1,173 @@
+//! UI-agnostic recording/transcription API.
+//!
+//! Wraps `AudioRecorder`, `WhisperTranscriber` and `MinutesFormatter` behind
+//! a handful of plain async functions operating on one shared session, so a
+//! `flutter_rust_bridge`-generated Dart front end can drive the same core
+//! the druid desktop app (`ui::recording_widget`) uses, without either side
+//! depending on the other's UI framework. Every type here is a plain
+//! struct/enum over primitives so codegen doesn't choke on it; the druid
+//! app can be migrated onto these functions incrementally.
+
+use crate::audio::recorder::{self, AudioRecorder, RecorderEvent};
+use crate::formatter::minutes::MinutesFormatter;
+use crate::speech::transcriber::{TranscriptionResult, WhisperTranscriber};
+use anyhow::Result;
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::mpsc;
+
+/// Capacity of the channel the recording session buffers partial-result
+/// events into between `poll_events` calls.
+const EVENT_CHANNEL_CAPACITY: usize = 32;
+
+/// Outcome of `stop_recording`, mirroring `audio::recorder::RecordingOutcome`
+/// with a bridge-friendly `String` path in place of `PathBuf`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordingOutcome {
+    Saved(String),
+    Empty,
+}
+
+/// Progress/result event for a session, mirroring `ui::app::AppEvent` but
+/// free of any druid dependency so it can cross the bridge.
+#[derive(Debug, Clone)]
+pub enum RecordingEvent {
+    PartialTranscription { committed: String, pending: String },
+    Error(String),
+}
+
+struct Session {
+    recorder: Arc<Mutex<AudioRecorder>>,
+    transcriber: Arc<Mutex<WhisperTranscriber>>,
+    formatter: MinutesFormatter,
+    events: Mutex<Option<mpsc::Receiver<RecorderEvent>>>,
+}
+
+static SESSION: OnceLock<Session> = OnceLock::new();
+
+fn session() -> Result<&'static Session> {
+    if let Some(session) = SESSION.get() {
+        return Ok(session);
+    }
+
+    let transcriber = WhisperTranscriber::default()?;
+    let session = Session {
+        recorder: Arc::new(Mutex::new(AudioRecorder::default())),
+        transcriber: Arc::new(Mutex::new(transcriber)),
+        formatter: MinutesFormatter::new(),
+        events: Mutex::new(None),
+    };
+
+    Ok(SESSION.get_or_init(|| session))
+}
+
+/// Downloads/loads the Whisper model if it isn't already. Callers don't
+/// have to invoke this explicitly - `start_recording` and `transcribe_bytes`
+/// load it lazily - but a front end may want to call it up front to show a
+/// loading indicator before the user starts recording.
+pub async fn load_model() -> Result<()> {
+    let session = session()?;
+    let transcriber = Arc::clone(&session.transcriber);
+    let mut transcriber = transcriber.lock().unwrap();
+    transcriber.load_model().await
+}
+
+/// Selects which input device and sample rate subsequent recordings use.
+/// `device_name` must be one of the names returned by `list_input_devices`,
+/// or `None` for the host default.
+pub fn select_device(device_name: Option<String>, sample_rate: u32) -> Result<()> {
+    let session = session()?;
+    let mut recorder = session.recorder.lock().unwrap();
+    recorder.set_device(device_name);
+    recorder.set_sample_rate(sample_rate);
+    Ok(())
+}
+
+/// Enumerates available capture devices and the sample rates each supports.
+pub fn list_input_devices() -> Result<Vec<recorder::InputDeviceInfo>> {
+    AudioRecorder::list_input_devices()
+}
+
+/// Starts capturing audio. Partial transcription results and errors arrive
+/// through `poll_events` while recording is in progress.
+pub async fn start_recording() -> Result<()> {
+    let session = session()?;
+    load_model().await?;
+
+    let (sender, receiver) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+    *session.events.lock().unwrap() = Some(receiver);
+
+    let transcriber = Arc::clone(&session.transcriber);
+    session
+        .recorder
+        .lock()
+        .unwrap()
+        .start_recording(transcriber, sender)
+}
+
+/// Stops capturing audio, finalizes the still-unstable partial transcript,
+/// and writes the WAV file to disk (or discards it if effectively silent).
+pub async fn stop_recording() -> Result<RecordingOutcome> {
+    let session = session()?;
+    let outcome = session.recorder.lock().unwrap().stop_recording()?;
+    session.transcriber.lock().unwrap().finalize_partial();
+
+    Ok(match outcome {
+        recorder::RecordingOutcome::Saved(path) => {
+            RecordingOutcome::Saved(path.to_string_lossy().into_owned())
+        }
+        recorder::RecordingOutcome::Empty => RecordingOutcome::Empty,
+    })
+}
+
+/// Drains whatever partial-transcription or error events have arrived
+/// since the last call, for a front end to poll on its own timer - the same
+/// pattern `ui::recording_widget` uses internally with a druid `Timer`.
+pub fn poll_events() -> Vec<RecordingEvent> {
+    let mut events = Vec::new();
+
+    let session = match session() {
+        Ok(session) => session,
+        Err(_) => return events,
+    };
+
+    let mut guard = session.events.lock().unwrap();
+    let receiver = match guard.as_mut() {
+        Some(receiver) => receiver,
+        None => return events,
+    };
+
+    while let Ok(event) = receiver.try_recv() {
+        events.push(match event {
+            RecorderEvent::TranscriptionPartial { committed, pending } => {
+                RecordingEvent::PartialTranscription { committed, pending }
+            }
+            RecorderEvent::TranscriptionError(e) => RecordingEvent::Error(e),
+        });
+    }
+
+    events
+}
+
+/// Seconds of audio captured in the current (or just-finished) session.
+pub fn get_duration() -> Result<f64> {
+    Ok(session()?.recorder.lock().unwrap().get_duration())
+}
+
+/// Transcribes a complete in-memory audio buffer (not a live recording).
+/// `format_hint` is the file extension, e.g. `"wav"`, `"mp3"`, `"flac"`.
+pub async fn transcribe_bytes(
+    audio_bytes: Vec<u8>,
+    format_hint: String,
+) -> Result<TranscriptionResult> {
+    let session = session()?;
+    let transcriber = Arc::clone(&session.transcriber);
+    let mut transcriber = transcriber.lock().unwrap();
+    transcriber.transcribe_bytes(&audio_bytes, &format_hint).await
+}
+
+/// Renders a transcription as formatted meeting minutes, the same output
+/// the druid app writes to disk from its download button.
+pub fn format_minutes(transcription: &TranscriptionResult, title: Option<String>) -> Result<String> {
+    let session = session()?;
+    Ok(session.formatter.format_minutes(transcription, title.as_deref()))
+}
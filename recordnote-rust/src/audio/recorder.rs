@@ -1,12 +1,26 @@
+use super::vad::{SpeechRegion, VoiceActivityDetector};
+use crate::speech::transcriber::WhisperTranscriber;
 use anyhow::{Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, Host, Stream, StreamConfig};
 use hound::{WavSpec, WavWriter};
+use ringbuf::{Consumer, HeapRb, Producer};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 
+const LEVEL_METER_FRAME_SIZE: usize = 512;
+const SILENCE_RMS_THRESHOLD: f32 = 1e-3;
+const RING_BUFFER_SECONDS: usize = 5;
+const ROLLING_WINDOW_SECONDS: usize = 30;
+const CONSUMER_POLL_INTERVAL: Duration = Duration::from_millis(20);
+const PARTIAL_TRANSCRIPTION_INTERVAL: Duration = Duration::from_millis(500);
+const CONSUMER_DRAIN_CHUNK: usize = 4096;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RecordingState {
     Stopped,
@@ -14,12 +28,81 @@ pub enum RecordingState {
     Processing,
 }
 
+/// Result of finalizing a recording to disk.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordingOutcome {
+    /// The WAV file was written and kept at this path.
+    Saved(PathBuf),
+    /// No (or effectively silent) audio was captured; no file was kept.
+    Empty,
+}
+
+/// Standard sample rates to offer per device, filtered down to whatever a
+/// device's `supported_input_configs()` range actually covers.
+const CANDIDATE_SAMPLE_RATES: [u32; 6] = [8_000, 16_000, 22_050, 24_000, 44_100, 48_000];
+
+/// One enumerable capture source, with the sample rates it can be driven
+/// at (so the UI can steer users toward something close to the 16 kHz
+/// Whisper expects and avoid a resampling pass).
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputDeviceInfo {
+    pub name: String,
+    pub supported_sample_rates: Vec<u32>,
+}
+
+/// Events the background consumer thread emits while recording. Kept
+/// UI-agnostic so a widget or the bridge API in `crate::api` can translate
+/// them into its own event type.
+#[derive(Debug, Clone)]
+pub enum RecorderEvent {
+    TranscriptionPartial { committed: String, pending: String },
+    TranscriptionError(String),
+}
+
+/// Tracks running sum-of-squares and sample count so overall loudness can
+/// be judged at the end of a recording without keeping every sample.
+struct SilenceStats {
+    sum_sq: f64,
+    count: usize,
+}
+
+impl SilenceStats {
+    fn new() -> Self {
+        Self {
+            sum_sq: 0.0,
+            count: 0,
+        }
+    }
+
+    fn update(&mut self, samples: &[f32]) {
+        self.sum_sq += samples.iter().map(|&s| (s as f64) * (s as f64)).sum::<f64>();
+        self.count += samples.len();
+    }
+
+    fn is_silent(&self) -> bool {
+        if self.count == 0 {
+            return true;
+        }
+        let rms = (self.sum_sq / self.count as f64).sqrt() as f32;
+        rms < SILENCE_RMS_THRESHOLD
+    }
+}
+
 pub struct AudioRecorder {
     sample_rate: u32,
     channels: u16,
+    device_name: Option<String>,
     state: Arc<Mutex<RecordingState>>,
-    audio_data: Arc<Mutex<Vec<f32>>>,
     stream: Option<Stream>,
+    vad: Arc<Mutex<VoiceActivityDetector>>,
+    output_dir: PathBuf,
+    sample_count: Arc<AtomicUsize>,
+    silence_stats: Arc<Mutex<SilenceStats>>,
+    rolling_window: Arc<Mutex<VecDeque<f32>>>,
+    consumer_thread: Option<thread::JoinHandle<()>>,
+    consumer_running: Arc<AtomicBool>,
+    wav_path: Arc<Mutex<Option<PathBuf>>>,
+    partial_thread: Option<thread::JoinHandle<()>>,
 }
 
 impl AudioRecorder {
@@ -27,9 +110,18 @@ impl AudioRecorder {
         Self {
             sample_rate,
             channels,
+            device_name: None,
             state: Arc::new(Mutex::new(RecordingState::Stopped)),
-            audio_data: Arc::new(Mutex::new(Vec::new())),
             stream: None,
+            vad: Arc::new(Mutex::new(VoiceActivityDetector::new(sample_rate))),
+            output_dir: PathBuf::from("recordings"),
+            sample_count: Arc::new(AtomicUsize::new(0)),
+            silence_stats: Arc::new(Mutex::new(SilenceStats::new())),
+            rolling_window: Arc::new(Mutex::new(VecDeque::new())),
+            consumer_thread: None,
+            consumer_running: Arc::new(AtomicBool::new(false)),
+            wav_path: Arc::new(Mutex::new(None)),
+            partial_thread: None,
         }
     }
 
@@ -37,7 +129,90 @@ impl AudioRecorder {
         Self::new(44100, 1)
     }
 
-    pub fn start_recording(&mut self) -> Result<()> {
+    /// Directory new recordings are saved into. Defaults to `./recordings`.
+    pub fn set_output_dir(&mut self, dir: PathBuf) {
+        self.output_dir = dir;
+    }
+
+    /// Enumerates available capture devices and, for each, the standard
+    /// sample rates it supports, so a UI can let the user pick a device
+    /// and a rate close to 16 kHz instead of always using the default.
+    pub fn list_input_devices() -> Result<Vec<InputDeviceInfo>> {
+        let host = cpal::default_host();
+        let mut devices = Vec::new();
+
+        for device in host.input_devices().context("Error listing input devices")? {
+            let name = match device.name() {
+                Ok(name) => name,
+                Err(_) => continue,
+            };
+
+            let supported_sample_rates = match device.supported_input_configs() {
+                Ok(configs) => {
+                    let configs: Vec<_> = configs.collect();
+                    CANDIDATE_SAMPLE_RATES
+                        .iter()
+                        .copied()
+                        .filter(|rate| {
+                            configs.iter().any(|c| {
+                                c.min_sample_rate().0 <= *rate && c.max_sample_rate().0 >= *rate
+                            })
+                        })
+                        .collect()
+                }
+                Err(_) => Vec::new(),
+            };
+
+            devices.push(InputDeviceInfo {
+                name,
+                supported_sample_rates,
+            });
+        }
+
+        Ok(devices)
+    }
+
+    /// Selects which capture device to record from by name, as returned by
+    /// `list_input_devices()`. Pass `None` to fall back to the host's
+    /// default input device. Has no effect on a recording already in
+    /// progress.
+    pub fn set_device(&mut self, device_name: Option<String>) {
+        self.device_name = device_name;
+    }
+
+    /// Changes the capture sample rate. Has no effect on a recording
+    /// already in progress.
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate;
+    }
+
+    fn resolve_device(&self, host: &Host) -> Result<Device> {
+        match &self.device_name {
+            Some(name) => host
+                .input_devices()
+                .context("Error listing input devices")?
+                .find(|d| d.name().map(|n| &n == name).unwrap_or(false))
+                .with_context(|| format!("Input device not found: {}", name)),
+            None => host.default_input_device().context("No input device available"),
+        }
+    }
+
+    /// Starts capturing audio. The cpal input callback pushes samples into
+    /// a lock-free SPSC ring buffer; a dedicated consumer thread drains it
+    /// and streams the WAV file to disk. That thread never runs inference
+    /// itself - every `PARTIAL_TRANSCRIPTION_INTERVAL` it hands a snapshot
+    /// of the rolling window to a separate partial-transcription worker
+    /// thread over a bounded channel and moves straight back to draining,
+    /// so a multi-second Whisper decode can never stall WAV writing and
+    /// overflow the 5-second ring buffer. The worker runs `transcriber`
+    /// and reports results through `event_sender`. The full session is
+    /// never held in one `Vec`, so memory stays bounded regardless of
+    /// duration.
+    pub fn start_recording(
+        &mut self,
+        transcriber: Arc<Mutex<WhisperTranscriber>>,
+        event_sender: mpsc::Sender<RecorderEvent>,
+    ) -> Result<()> {
         {
             let mut state = self.state.lock().unwrap();
             if *state != RecordingState::Stopped {
@@ -46,27 +221,43 @@ impl AudioRecorder {
             *state = RecordingState::Recording;
         }
 
-        // Clear previous audio data
-        self.audio_data.lock().unwrap().clear();
+        self.sample_count.store(0, Ordering::SeqCst);
+        *self.silence_stats.lock().unwrap() = SilenceStats::new();
+        self.rolling_window.lock().unwrap().clear();
+
+        std::fs::create_dir_all(&self.output_dir)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = self.output_dir.join(format!("recording_{}.wav", timestamp));
+        *self.wav_path.lock().unwrap() = Some(path.clone());
+
+        let spec = WavSpec {
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let writer = WavWriter::create(&path, spec)?;
+
+        let ring_capacity =
+            self.sample_rate as usize * self.channels as usize * RING_BUFFER_SECONDS;
+        let ring_buffer = HeapRb::<f32>::new(ring_capacity);
+        let (mut producer, mut consumer) = ring_buffer.split();
 
         // Setup audio input
         let host = cpal::default_host();
-        let device = host
-            .default_input_device()
-            .context("No input device available")?;
+        let device = self.resolve_device(&host)?;
 
         let config = self.get_stream_config(&device)?;
 
-        let audio_data = Arc::clone(&self.audio_data);
-        let state = Arc::clone(&self.state);
-
+        let state_for_callback = Arc::clone(&self.state);
         let stream = device.build_input_stream(
             &config,
             move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                let state_guard = state.lock().unwrap();
-                if *state_guard == RecordingState::Recording {
-                    let mut audio_guard = audio_data.lock().unwrap();
-                    audio_guard.extend_from_slice(data);
+                if *state_for_callback.lock().unwrap() == RecordingState::Recording {
+                    producer.push_slice(data);
                 }
             },
             move |err| {
@@ -78,10 +269,95 @@ impl AudioRecorder {
         stream.play()?;
         self.stream = Some(stream);
 
+        self.consumer_running.store(true, Ordering::SeqCst);
+        let consumer_running = Arc::clone(&self.consumer_running);
+        let sample_count = Arc::clone(&self.sample_count);
+        let silence_stats = Arc::clone(&self.silence_stats);
+        let rolling_window = Arc::clone(&self.rolling_window);
+        let state_for_consumer = Arc::clone(&self.state);
+        let window_capacity =
+            self.sample_rate as usize * self.channels as usize * ROLLING_WINDOW_SECONDS;
+        let capture_sample_rate = self.sample_rate;
+
+        // Bounded to one slot: the drain thread never blocks handing off a
+        // snapshot, and if the worker is still decoding the previous one
+        // the new snapshot is simply dropped in favor of the next tick.
+        let (partial_tx, partial_rx) = std::sync::mpsc::sync_channel::<Vec<f32>>(1);
+        let partial_event_sender = event_sender;
+
+        self.partial_thread = Some(thread::spawn(move || {
+            while let Ok(snapshot) = partial_rx.recv() {
+                let mut transcriber_guard = match transcriber.lock() {
+                    Ok(guard) => guard,
+                    Err(_) => break,
+                };
+                match transcriber_guard.transcribe_partial(&snapshot, capture_sample_rate) {
+                    Ok(partial) => {
+                        let _ = partial_event_sender.try_send(RecorderEvent::TranscriptionPartial {
+                            committed: partial.committed_text,
+                            pending: partial.pending_text,
+                        });
+                    }
+                    Err(e) => {
+                        let _ = partial_event_sender
+                            .try_send(RecorderEvent::TranscriptionError(e.to_string()));
+                    }
+                }
+            }
+        }));
+
+        self.consumer_thread = Some(thread::spawn(move || {
+            let mut writer = writer;
+            let mut drain_buf = vec![0.0f32; CONSUMER_DRAIN_CHUNK];
+            let mut last_partial = Instant::now();
+
+            loop {
+                let popped = consumer.pop_slice(&mut drain_buf);
+
+                if popped > 0 {
+                    let chunk = &drain_buf[..popped];
+
+                    for &sample in chunk {
+                        let _ = writer.write_sample(sample);
+                    }
+
+                    sample_count.fetch_add(popped, Ordering::SeqCst);
+                    silence_stats.lock().unwrap().update(chunk);
+
+                    let mut window = rolling_window.lock().unwrap();
+                    window.extend(chunk.iter().copied());
+                    while window.len() > window_capacity {
+                        window.pop_front();
+                    }
+                } else if !consumer_running.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let still_recording =
+                    *state_for_consumer.lock().unwrap() == RecordingState::Recording;
+                if still_recording && last_partial.elapsed() >= PARTIAL_TRANSCRIPTION_INTERVAL {
+                    last_partial = Instant::now();
+                    let snapshot: Vec<f32> =
+                        rolling_window.lock().unwrap().iter().copied().collect();
+
+                    // Non-blocking hand-off: if the worker is still busy
+                    // with the previous snapshot, drop this one rather than
+                    // stalling the drain loop.
+                    let _ = partial_tx.try_send(snapshot);
+                }
+
+                if popped == 0 {
+                    thread::sleep(CONSUMER_POLL_INTERVAL);
+                }
+            }
+
+            let _ = writer.finalize();
+        }));
+
         Ok(())
     }
 
-    pub fn stop_recording(&mut self) -> Result<()> {
+    pub fn stop_recording(&mut self) -> Result<RecordingOutcome> {
         {
             let mut state = self.state.lock().unwrap();
             if *state != RecordingState::Recording {
@@ -90,20 +366,47 @@ impl AudioRecorder {
             *state = RecordingState::Processing;
         }
 
-        // Stop the stream
+        // Stop the stream so the callback stops producing into the ring
+        // buffer, then let the consumer thread drain what's left and exit.
         if let Some(stream) = self.stream.take() {
             drop(stream);
         }
 
-        // Small delay to ensure all data is captured
         thread::sleep(Duration::from_millis(100));
+        self.consumer_running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.consumer_thread.take() {
+            let _ = handle.join();
+        }
+        // The drain thread above owns the only `partial_tx`; once it exits,
+        // dropping that sender unblocks the worker's `recv()` so it exits too.
+        if let Some(handle) = self.partial_thread.take() {
+            let _ = handle.join();
+        }
+
+        let outcome = self.finalize_outcome();
 
         {
             let mut state = self.state.lock().unwrap();
             *state = RecordingState::Stopped;
         }
 
-        Ok(())
+        Ok(outcome)
+    }
+
+    /// Deletes the streamed WAV file if the session turned out to be
+    /// empty or effectively silent; otherwise returns its path.
+    fn finalize_outcome(&self) -> RecordingOutcome {
+        let path = self.wav_path.lock().unwrap().clone();
+        let is_silent = self.silence_stats.lock().unwrap().is_silent();
+
+        match path {
+            Some(path) if !is_silent => RecordingOutcome::Saved(path),
+            Some(path) => {
+                let _ = std::fs::remove_file(&path);
+                RecordingOutcome::Empty
+            }
+            None => RecordingOutcome::Empty,
+        }
     }
 
     pub fn is_recording(&self) -> bool {
@@ -114,65 +417,37 @@ impl AudioRecorder {
         *self.state.lock().unwrap()
     }
 
-    pub fn save_to_file(&self, path: &str) -> Result<()> {
-        let audio_data = self.audio_data.lock().unwrap();
-        
-        if audio_data.is_empty() {
-            return Err(anyhow::anyhow!("No audio data to save"));
-        }
-
-        let spec = WavSpec {
-            channels: self.channels,
-            sample_rate: self.sample_rate,
-            bits_per_sample: 32,
-            sample_format: hound::SampleFormat::Float,
-        };
-
-        let mut writer = WavWriter::create(path, spec)?;
-
-        for &sample in audio_data.iter() {
-            writer.write_sample(sample)?;
-        }
-
-        writer.finalize()?;
-        Ok(())
+    /// Returns the most recent `ROLLING_WINDOW_SECONDS` of captured audio.
+    /// Bounded, unlike holding the whole session in memory.
+    pub fn get_audio_snapshot(&self) -> Vec<f32> {
+        self.rolling_window.lock().unwrap().iter().copied().collect()
     }
 
-    pub fn get_audio_bytes(&self) -> Result<Vec<u8>> {
-        let audio_data = self.audio_data.lock().unwrap();
-        
-        if audio_data.is_empty() {
-            return Err(anyhow::anyhow!("No audio data available"));
-        }
-
-        let spec = WavSpec {
-            channels: self.channels,
-            sample_rate: self.sample_rate,
-            bits_per_sample: 32,
-            sample_format: hound::SampleFormat::Float,
-        };
+    /// Runs voice-activity detection over the current rolling window and
+    /// returns the detected speech regions, so callers can skip leading
+    /// and trailing silence or pre-seed transcription segment boundaries.
+    pub fn detect_speech_regions(&self) -> Vec<SpeechRegion> {
+        let samples = self.get_audio_snapshot();
+        self.vad.lock().unwrap().detect_regions(&samples)
+    }
 
-        let mut buffer = Vec::new();
-        {
-            let mut writer = WavWriter::new(std::io::Cursor::new(&mut buffer), spec)?;
-            
-            for &sample in audio_data.iter() {
-                writer.write_sample(sample)?;
-            }
-            
-            writer.finalize()?;
+    /// Energy of the most recently captured frame, for a live level meter.
+    pub fn current_frame_energy(&self) -> f32 {
+        let window = self.rolling_window.lock().unwrap();
+        if window.len() < LEVEL_METER_FRAME_SIZE {
+            return 0.0;
         }
 
-        Ok(buffer)
+        let frame: Vec<f32> = window
+            .iter()
+            .skip(window.len() - LEVEL_METER_FRAME_SIZE)
+            .copied()
+            .collect();
+        self.vad.lock().unwrap().frame_energy(&frame)
     }
 
     pub fn get_duration(&self) -> f64 {
-        let audio_data = self.audio_data.lock().unwrap();
-        if audio_data.is_empty() {
-            return 0.0;
-        }
-
-        let total_frames = audio_data.len() as f64 / self.channels as f64;
+        let total_frames = self.sample_count.load(Ordering::SeqCst) as f64 / self.channels as f64;
         total_frames / self.sample_rate as f64
     }
 
@@ -206,4 +481,4 @@ impl AudioRecorder {
             buffer_size: cpal::BufferSize::Default,
         })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,212 @@
+use realfft::{RealFftPlanner, RealToComplex};
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+const FRAME_SIZE: usize = 512;
+const HOP_SIZE: usize = FRAME_SIZE / 2;
+const HANGOVER_MS: f64 = 300.0;
+const NOISE_FLOOR_RISE_RATE: f32 = 0.02;
+const DEFAULT_THRESHOLD_FACTOR: f32 = 3.0;
+
+/// A contiguous span of samples classified as speech, in sample indices
+/// relative to the start of the processed buffer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeechRegion {
+    pub start_sample: usize,
+    pub end_sample: usize,
+}
+
+/// Frame-by-frame voice activity detector. Classifies 512-sample,
+/// 50%-overlapped frames as speech or silence using summed FFT
+/// magnitude-squared energy against an adaptive noise floor, then merges
+/// adjacent speech frames into regions with a short hangover so brief
+/// pauses within speech don't split a segment.
+pub struct VoiceActivityDetector {
+    sample_rate: u32,
+    threshold_factor: f32,
+    noise_floor: f32,
+    window: Vec<f32>,
+    hangover_frames: usize,
+    fft: Arc<dyn RealToComplex<f32>>,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(sample_rate: u32) -> Self {
+        let hop_duration_ms = (HOP_SIZE as f64 / sample_rate as f64) * 1000.0;
+        let hangover_frames = (HANGOVER_MS / hop_duration_ms).ceil().max(1.0) as usize;
+
+        Self {
+            sample_rate,
+            threshold_factor: DEFAULT_THRESHOLD_FACTOR,
+            noise_floor: f32::MAX,
+            window: hann_window(FRAME_SIZE),
+            hangover_frames,
+            fft: RealFftPlanner::<f32>::new().plan_fft_forward(FRAME_SIZE),
+        }
+    }
+
+    pub fn with_threshold_factor(mut self, factor: f32) -> Self {
+        self.threshold_factor = factor;
+        self
+    }
+
+    /// Computes the summed magnitude-squared FFT energy of a single
+    /// Hann-windowed frame. `frame` is padded with zeros if shorter than
+    /// `FRAME_SIZE`.
+    pub fn frame_energy(&self, frame: &[f32]) -> f32 {
+        let mut windowed = vec![0.0f32; FRAME_SIZE];
+        for (i, &sample) in frame.iter().take(FRAME_SIZE).enumerate() {
+            windowed[i] = sample * self.window[i];
+        }
+
+        let mut spectrum = self.fft.make_output_vec();
+
+        if self.fft.process(&mut windowed, &mut spectrum).is_err() {
+            return 0.0;
+        }
+
+        spectrum.iter().map(|c| c.norm_sqr()).sum()
+    }
+
+    /// Updates the adaptive noise floor with a newly observed frame energy
+    /// and returns whether the frame should be classified as speech.
+    fn classify(&mut self, energy: f32) -> bool {
+        if self.noise_floor == f32::MAX {
+            self.noise_floor = energy;
+        } else if energy < self.noise_floor {
+            self.noise_floor = energy;
+        } else {
+            self.noise_floor += (energy - self.noise_floor) * NOISE_FLOOR_RISE_RATE;
+        }
+
+        energy > self.noise_floor * self.threshold_factor
+    }
+
+    /// Splits `samples` into speech regions, merging adjacent speech
+    /// frames and applying a hangover so short in-speech pauses don't
+    /// fragment a region.
+    pub fn detect_regions(&mut self, samples: &[f32]) -> Vec<SpeechRegion> {
+        if samples.len() < FRAME_SIZE {
+            return Vec::new();
+        }
+
+        let mut regions = Vec::new();
+        let mut region_start: Option<usize> = None;
+        let mut silence_run = 0usize;
+
+        let mut offset = 0;
+        while offset + FRAME_SIZE <= samples.len() {
+            let frame = &samples[offset..offset + FRAME_SIZE];
+            let energy = self.frame_energy(frame);
+            let is_speech = self.classify(energy);
+
+            if is_speech {
+                silence_run = 0;
+                if region_start.is_none() {
+                    region_start = Some(offset);
+                }
+            } else if let Some(start) = region_start {
+                silence_run += 1;
+                if silence_run > self.hangover_frames {
+                    regions.push(SpeechRegion {
+                        start_sample: start,
+                        end_sample: last_voiced_frame_end(offset, silence_run),
+                    });
+                    region_start = None;
+                    silence_run = 0;
+                }
+            }
+
+            offset += HOP_SIZE;
+        }
+
+        if let Some(start) = region_start {
+            regions.push(SpeechRegion {
+                start_sample: start,
+                end_sample: samples.len(),
+            });
+        }
+
+        regions
+    }
+
+    /// Current running noise floor estimate, exposed so the UI can draw a
+    /// live level meter relative to it.
+    pub fn noise_floor(&self) -> f32 {
+        self.noise_floor
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
+/// The end sample of the last frame classified as speech before a region
+/// closed, given the current frame `offset` (the latest of the silent
+/// frames that closed the region) and how many consecutive silent frames
+/// have been counted (`silence_run`). The last voiced frame started `silence_run` hops
+/// before `offset`, so it ends `FRAME_SIZE` samples past that start.
+fn last_voiced_frame_end(offset: usize, silence_run: usize) -> usize {
+    offset - silence_run * HOP_SIZE + FRAME_SIZE
+}
+
+fn hann_window(size: usize) -> Vec<f32> {
+    (0..size)
+        .map(|i| 0.5 - 0.5 * ((2.0 * PI * i as f32) / (size as f32 - 1.0)).cos())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine_wave(freq: f32, sample_rate: u32, len: usize, amplitude: f32) -> Vec<f32> {
+        (0..len)
+            .map(|i| amplitude * (2.0 * PI * freq * i as f32 / sample_rate as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_frame_energy_is_higher_for_louder_frames() {
+        let vad = VoiceActivityDetector::new(16000);
+        let quiet = sine_wave(440.0, 16000, FRAME_SIZE, 0.01);
+        let loud = sine_wave(440.0, 16000, FRAME_SIZE, 0.5);
+
+        assert!(vad.frame_energy(&loud) > vad.frame_energy(&quiet));
+    }
+
+    #[test]
+    fn test_detect_regions_finds_speech_amid_silence() {
+        let sample_rate = 16000;
+        let mut vad = VoiceActivityDetector::new(sample_rate);
+
+        let silence = vec![0.0f32; sample_rate as usize];
+        let speech = sine_wave(300.0, sample_rate, sample_rate as usize, 0.8);
+
+        let mut samples = silence.clone();
+        samples.extend(speech);
+        samples.extend(silence);
+
+        let regions = vad.detect_regions(&samples);
+
+        assert!(!regions.is_empty());
+        for region in &regions {
+            assert!(region.start_sample < region.end_sample);
+        }
+    }
+
+    #[test]
+    fn test_last_voiced_frame_end_accounts_for_the_full_silence_run() {
+        // Region closes on the frame at offset 5000 after 4 consecutive
+        // silent hops (silence_run = 4); the last voiced frame therefore
+        // started 4 hops earlier, at 5000 - 4*256 = 3976, and its own
+        // window ends FRAME_SIZE samples after that start.
+        let offset = 5000;
+        let silence_run = 4;
+
+        let end = last_voiced_frame_end(offset, silence_run);
+
+        assert_eq!(end, offset - silence_run * HOP_SIZE + FRAME_SIZE);
+        assert_eq!(end, 3976 + FRAME_SIZE);
+    }
+}
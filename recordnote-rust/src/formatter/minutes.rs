@@ -132,6 +132,77 @@ impl MinutesFormatter {
         format!("{:02}:{:02}", minutes, secs)
     }
 
+    /// Format segments as an SRT subtitle file.
+    pub fn format_srt(&self, transcription: &TranscriptionResult) -> String {
+        let mut srt = String::new();
+        let mut cue_number = 1;
+
+        for segment in transcription.segments.iter() {
+            let text = segment.text.trim();
+            if text.is_empty() {
+                continue;
+            }
+
+            srt.push_str(&format!("{}\n", cue_number));
+            cue_number += 1;
+            srt.push_str(&format!(
+                "{} --> {}\n",
+                self.format_srt_timestamp(segment.start),
+                self.format_srt_timestamp(segment.end)
+            ));
+            srt.push_str(text);
+            srt.push_str("\n\n");
+        }
+
+        srt
+    }
+
+    /// Format segments as a WebVTT subtitle file.
+    pub fn format_vtt(&self, transcription: &TranscriptionResult) -> String {
+        let mut vtt = String::from("WEBVTT\n\n");
+        let mut cue_number = 1;
+
+        for segment in transcription.segments.iter() {
+            let text = segment.text.trim();
+            if text.is_empty() {
+                continue;
+            }
+
+            vtt.push_str(&format!("{}\n", cue_number));
+            cue_number += 1;
+            vtt.push_str(&format!(
+                "{} --> {}\n",
+                self.format_vtt_timestamp(segment.start),
+                self.format_vtt_timestamp(segment.end)
+            ));
+            vtt.push_str(text);
+            vtt.push_str("\n\n");
+        }
+
+        vtt
+    }
+
+    /// Format `seconds` as `HH:MM:SS,mmm` for SRT cues.
+    fn format_srt_timestamp(&self, seconds: f64) -> String {
+        let (hours, minutes, secs, millis) = self.split_timestamp(seconds);
+        format!("{:02}:{:02}:{:02},{:03}", hours, minutes, secs, millis)
+    }
+
+    /// Format `seconds` as `HH:MM:SS.mmm` for WebVTT cues.
+    fn format_vtt_timestamp(&self, seconds: f64) -> String {
+        let (hours, minutes, secs, millis) = self.split_timestamp(seconds);
+        format!("{:02}:{:02}:{:02}.{:03}", hours, minutes, secs, millis)
+    }
+
+    fn split_timestamp(&self, seconds: f64) -> (u32, u32, u32, u32) {
+        let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+        let hours = (total_millis / 3_600_000) as u32;
+        let minutes = ((total_millis / 60_000) % 60) as u32;
+        let secs = ((total_millis / 1000) % 60) as u32;
+        let millis = (total_millis % 1000) as u32;
+        (hours, minutes, secs, millis)
+    }
+
     fn clean_text(&self, text: &str) -> String {
         if text.is_empty() {
             return String::new();
@@ -218,6 +289,80 @@ mod tests {
         assert!(result.contains("これは") || result.contains("テスト") || result.contains("です"));
     }
 
+    #[test]
+    fn test_format_srt() {
+        let formatter = MinutesFormatter::new();
+
+        let transcription = TranscriptionResult {
+            text: "これはテストです。".to_string(),
+            language: "ja".to_string(),
+            segments: vec![TranscriptionSegment {
+                start: 1.25,
+                end: 3.6,
+                text: "これはテストです。".to_string(),
+            }],
+        };
+
+        let srt = formatter.format_srt(&transcription);
+
+        assert!(srt.starts_with("1\n"));
+        assert!(srt.contains("00:00:01,250 --> 00:00:03,600"));
+        assert!(srt.contains("これはテストです。"));
+    }
+
+    #[test]
+    fn test_format_srt_numbers_cues_sequentially_past_blank_segments() {
+        let formatter = MinutesFormatter::new();
+
+        let transcription = TranscriptionResult {
+            text: "一 二".to_string(),
+            language: "ja".to_string(),
+            segments: vec![
+                TranscriptionSegment {
+                    start: 0.0,
+                    end: 1.0,
+                    text: "一".to_string(),
+                },
+                TranscriptionSegment {
+                    start: 1.0,
+                    end: 1.0,
+                    text: "".to_string(),
+                },
+                TranscriptionSegment {
+                    start: 1.0,
+                    end: 2.0,
+                    text: "二".to_string(),
+                },
+            ],
+        };
+
+        let srt = formatter.format_srt(&transcription);
+
+        assert!(srt.contains("1\n00:00:00,000"));
+        assert!(srt.contains("2\n00:00:01,000"));
+        assert!(!srt.contains("3\n"));
+    }
+
+    #[test]
+    fn test_format_vtt() {
+        let formatter = MinutesFormatter::new();
+
+        let transcription = TranscriptionResult {
+            text: "これはテストです。".to_string(),
+            language: "ja".to_string(),
+            segments: vec![TranscriptionSegment {
+                start: 0.0,
+                end: 3661.5,
+                text: "これはテストです。".to_string(),
+            }],
+        };
+
+        let vtt = formatter.format_vtt(&transcription);
+
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt.contains("00:00:00.000 --> 01:01:01.500"));
+    }
+
     #[test]
     fn test_summary_stats() {
         let formatter = MinutesFormatter::new();
@@ -0,0 +1,3 @@
+pub mod minutes;
+pub mod output_format;
+pub mod segment_editor;
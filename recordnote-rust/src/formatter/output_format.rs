@@ -0,0 +1,227 @@
+use super::minutes::{MinutesFormatter, SummaryStats};
+use crate::speech::transcriber::TranscriptionResult;
+use anyhow::Result;
+use serde::Serialize;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A pluggable rendering for a `TranscriptionResult`. New output formats
+/// implement this instead of the export path knowing about each one.
+pub trait OutputFormat {
+    fn render(&self, transcription: &TranscriptionResult, title: Option<&str>) -> String;
+    fn extension(&self) -> &str;
+}
+
+/// The existing Markdown minutes layout.
+pub struct MarkdownFormat {
+    formatter: MinutesFormatter,
+}
+
+impl MarkdownFormat {
+    pub fn new() -> Self {
+        Self {
+            formatter: MinutesFormatter::new(),
+        }
+    }
+}
+
+impl Default for MarkdownFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OutputFormat for MarkdownFormat {
+    fn render(&self, transcription: &TranscriptionResult, title: Option<&str>) -> String {
+        self.formatter.format_minutes(transcription, title)
+    }
+
+    fn extension(&self) -> &str {
+        "md"
+    }
+}
+
+/// Plain text with no Markdown markup: the title (if given) on its own
+/// line, then the recognized text.
+pub struct PlainTextFormat;
+
+impl OutputFormat for PlainTextFormat {
+    fn render(&self, transcription: &TranscriptionResult, title: Option<&str>) -> String {
+        let mut text = String::new();
+
+        if let Some(title) = title {
+            text.push_str(title);
+            text.push_str("\n\n");
+        }
+
+        text.push_str(transcription.text.trim());
+        text
+    }
+
+    fn extension(&self) -> &str {
+        "txt"
+    }
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    title: Option<&'a str>,
+    transcription: &'a TranscriptionResult,
+    stats: SummaryStats,
+}
+
+/// The full `TranscriptionResult` plus `SummaryStats`, serialized as JSON.
+pub struct JsonFormat;
+
+impl OutputFormat for JsonFormat {
+    fn render(&self, transcription: &TranscriptionResult, title: Option<&str>) -> String {
+        let stats = MinutesFormatter::new().get_summary_stats(transcription);
+        let export = JsonExport {
+            title,
+            transcription,
+            stats,
+        };
+
+        serde_json::to_string_pretty(&export).unwrap_or_default()
+    }
+
+    fn extension(&self) -> &str {
+        "json"
+    }
+}
+
+/// One row per segment: start, end, text.
+pub struct CsvFormat;
+
+impl OutputFormat for CsvFormat {
+    fn render(&self, transcription: &TranscriptionResult, _title: Option<&str>) -> String {
+        let mut csv = String::from("start,end,text\n");
+
+        for segment in &transcription.segments {
+            csv.push_str(&format!(
+                "{:.3},{:.3},\"{}\"\n",
+                segment.start,
+                segment.end,
+                segment.text.replace('"', "\"\"")
+            ));
+        }
+
+        csv
+    }
+
+    fn extension(&self) -> &str {
+        "csv"
+    }
+}
+
+/// Identifies a registered output format so callers can select one by
+/// name (e.g. from a UI dropdown) without constructing the impl directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormatKind {
+    Markdown,
+    PlainText,
+    Json,
+    Csv,
+}
+
+impl OutputFormatKind {
+    pub fn formatter(&self) -> Box<dyn OutputFormat> {
+        match self {
+            OutputFormatKind::Markdown => Box::new(MarkdownFormat::new()),
+            OutputFormatKind::PlainText => Box::new(PlainTextFormat),
+            OutputFormatKind::Json => Box::new(JsonFormat),
+            OutputFormatKind::Csv => Box::new(CsvFormat),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "markdown" | "md" => Some(OutputFormatKind::Markdown),
+            "text" | "plain" | "txt" => Some(OutputFormatKind::PlainText),
+            "json" => Some(OutputFormatKind::Json),
+            "csv" => Some(OutputFormatKind::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Renders `transcription` with the requested format and writes it to
+/// `base_path` with its extension set to that format's extension (an
+/// existing extension is replaced), returning the final path that was
+/// written.
+pub fn export_to_file(
+    transcription: &TranscriptionResult,
+    title: Option<&str>,
+    format: OutputFormatKind,
+    base_path: &Path,
+) -> Result<PathBuf> {
+    let output = format.formatter();
+    let rendered = output.render(transcription, title);
+
+    let file_path = base_path.with_extension(output.extension());
+
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = File::create(&file_path)?;
+    file.write_all(rendered.as_bytes())?;
+    file.flush()?;
+
+    Ok(file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::speech::transcriber::TranscriptionSegment;
+
+    fn sample_transcription() -> TranscriptionResult {
+        TranscriptionResult {
+            text: "これはテストです。".to_string(),
+            language: "ja".to_string(),
+            segments: vec![TranscriptionSegment {
+                start: 0.0,
+                end: 2.0,
+                text: "これはテストです。".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_plain_text_format() {
+        let format = PlainTextFormat;
+        let rendered = format.render(&sample_transcription(), Some("テスト会議"));
+
+        assert_eq!(format.extension(), "txt");
+        assert!(rendered.starts_with("テスト会議"));
+        assert!(rendered.contains("これはテストです。"));
+    }
+
+    #[test]
+    fn test_json_format_round_trips() {
+        let format = JsonFormat;
+        let rendered = format.render(&sample_transcription(), None);
+
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(value["transcription"]["language"], "ja");
+        assert_eq!(value["stats"]["segment_count"], 1);
+    }
+
+    #[test]
+    fn test_csv_format() {
+        let format = CsvFormat;
+        let rendered = format.render(&sample_transcription(), None);
+
+        assert!(rendered.starts_with("start,end,text\n"));
+        assert!(rendered.contains("0.000,2.000"));
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(OutputFormatKind::from_name("md"), Some(OutputFormatKind::Markdown));
+        assert_eq!(OutputFormatKind::from_name("CSV"), Some(OutputFormatKind::Csv));
+        assert_eq!(OutputFormatKind::from_name("unknown"), None);
+    }
+}
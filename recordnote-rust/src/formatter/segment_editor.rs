@@ -0,0 +1,203 @@
+use crate::speech::transcriber::TranscriptionSegment;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+
+/// Restricts a `SegmentEditor` operation to a subset of segments, either by
+/// half-open index range or by a half-open time window that a segment's
+/// start time must fall in (its end time is not checked).
+#[derive(Debug, Clone)]
+pub enum EditRange {
+    Indices(usize, usize),
+    Time(f64, f64),
+}
+
+impl EditRange {
+    fn contains(&self, index: usize, segment: &TranscriptionSegment) -> bool {
+        match self {
+            EditRange::Indices(start, end) => index >= *start && index < *end,
+            EditRange::Time(start, end) => segment.start >= *start && segment.start < *end,
+        }
+    }
+}
+
+/// Adjusts the timing of transcription segments to correct for drift
+/// between the recognized timeline and a user's reference (e.g. a video
+/// they are syncing captions against).
+#[derive(Clone)]
+pub struct SegmentEditor {
+    // Configuration options could be added here
+}
+
+impl SegmentEditor {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Shifts every segment's start/end by a constant number of seconds,
+    /// clamping the result at 0.0. Positive `offset_seconds` moves segments
+    /// later, negative moves them earlier.
+    pub fn shift(
+        &self,
+        segments: &[TranscriptionSegment],
+        offset_seconds: f64,
+        range: Option<&EditRange>,
+    ) -> Vec<TranscriptionSegment> {
+        segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| {
+                if range.map_or(true, |r| r.contains(i, segment)) {
+                    TranscriptionSegment {
+                        start: (segment.start + offset_seconds).max(0.0),
+                        end: (segment.end + offset_seconds).max(0.0),
+                        text: segment.text.clone(),
+                    }
+                } else {
+                    segment.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Rescales segments with a two-point linear mapping `new = a * old + b`,
+    /// where `a` and `b` are derived from two (old_time, correct_time)
+    /// anchors supplied by the user.
+    pub fn rescale(
+        &self,
+        segments: &[TranscriptionSegment],
+        anchor_a: (f64, f64),
+        anchor_b: (f64, f64),
+        range: Option<&EditRange>,
+    ) -> Result<Vec<TranscriptionSegment>> {
+        let (old_a, new_a) = anchor_a;
+        let (old_b, new_b) = anchor_b;
+
+        if (old_b - old_a).abs() < f64::EPSILON {
+            return Err(anyhow!("Anchor points must have distinct old_time values"));
+        }
+
+        let a = (new_b - new_a) / (old_b - old_a);
+        let b = new_a - a * old_a;
+
+        Ok(segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| {
+                if range.map_or(true, |r| r.contains(i, segment)) {
+                    TranscriptionSegment {
+                        start: (a * segment.start + b).max(0.0),
+                        end: (a * segment.end + b).max(0.0),
+                        text: segment.text.clone(),
+                    }
+                } else {
+                    segment.clone()
+                }
+            })
+            .collect())
+    }
+
+    /// Parses a timestamp in `MM:SS.mmm`, `HH:MM:SS,mmm` or plain seconds
+    /// form, so values copy-pasted from SRT output can be reused directly.
+    pub fn parse_time(input: &str) -> Result<f64> {
+        let input = input.trim().replace(',', ".");
+
+        if let Ok(seconds) = input.parse::<f64>() {
+            return Ok(seconds);
+        }
+
+        let re = Regex::new(r"^(?:(\d+):)?(\d{1,2}):(\d{1,2}(?:\.\d+)?)$").unwrap();
+        let captures = re
+            .captures(&input)
+            .ok_or_else(|| anyhow!("Invalid timestamp: {}", input))?;
+
+        let hours: f64 = captures
+            .get(1)
+            .map(|m| m.as_str().parse().unwrap_or(0.0))
+            .unwrap_or(0.0);
+        let minutes: f64 = captures[2].parse()?;
+        let seconds: f64 = captures[3].parse()?;
+
+        Ok(hours * 3600.0 + minutes * 60.0 + seconds)
+    }
+}
+
+impl Default for SegmentEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments() -> Vec<TranscriptionSegment> {
+        vec![
+            TranscriptionSegment {
+                start: 0.0,
+                end: 2.0,
+                text: "one".to_string(),
+            },
+            TranscriptionSegment {
+                start: 2.0,
+                end: 4.0,
+                text: "two".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_shift_clamps_at_zero() {
+        let editor = SegmentEditor::new();
+        let shifted = editor.shift(&segments(), -5.0, None);
+
+        assert_eq!(shifted[0].start, 0.0);
+        assert_eq!(shifted[0].end, 0.0);
+        assert_eq!(shifted[1].start, 0.0);
+    }
+
+    #[test]
+    fn test_shift_positive_offset() {
+        let editor = SegmentEditor::new();
+        let shifted = editor.shift(&segments(), 1.5, None);
+
+        assert_eq!(shifted[0].start, 1.5);
+        assert_eq!(shifted[1].end, 5.5);
+    }
+
+    #[test]
+    fn test_rescale_from_anchors() {
+        let editor = SegmentEditor::new();
+        let rescaled = editor
+            .rescale(&segments(), (0.0, 1.0), (4.0, 5.0), None)
+            .unwrap();
+
+        assert_eq!(rescaled[0].start, 1.0);
+        assert_eq!(rescaled[1].end, 5.0);
+    }
+
+    #[test]
+    fn test_rescale_rejects_degenerate_anchors() {
+        let editor = SegmentEditor::new();
+        let result = editor.rescale(&segments(), (1.0, 1.0), (1.0, 2.0), None);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_range_restricts_edit() {
+        let editor = SegmentEditor::new();
+        let shifted = editor.shift(&segments(), 1.0, Some(&EditRange::Indices(1, 2)));
+
+        assert_eq!(shifted[0].start, 0.0);
+        assert_eq!(shifted[1].start, 3.0);
+    }
+
+    #[test]
+    fn test_parse_time_formats() {
+        assert_eq!(SegmentEditor::parse_time("01:02.500").unwrap(), 62.5);
+        assert_eq!(SegmentEditor::parse_time("01:01:01,250").unwrap(), 3661.25);
+        assert_eq!(SegmentEditor::parse_time("5.0").unwrap(), 5.0);
+        assert!(SegmentEditor::parse_time("not-a-time").is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::path::Path;
+use symphonia::core::audio::{SampleBuffer, SignalSpec};
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// Decodes any container/codec symphonia supports (MP3, FLAC, OGG, M4A,
+/// WAV, ...) to interleaved `f32` samples, probing from the file
+/// extension and header rather than assuming WAV.
+pub fn decode_interleaved(path: &Path) -> Result<(Vec<f32>, SignalSpec)> {
+    let file = File::open(path)?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        mss,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != symphonia::core::codecs::CODEC_TYPE_NULL)
+        .ok_or_else(|| anyhow!("No decodable audio track found in {:?}", path))?;
+    let track_id = track.id;
+
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut samples = Vec::new();
+    let mut spec: Option<SignalSpec> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(_)) => break, // end of stream
+            Err(e) => return Err(e.into()),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = decoder.decode(&packet)?;
+        if spec.is_none() {
+            spec = Some(*decoded.spec());
+        }
+
+        let mut sample_buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+        sample_buffer.copy_interleaved_ref(decoded);
+        samples.extend_from_slice(sample_buffer.samples());
+    }
+
+    let spec = spec.ok_or_else(|| anyhow!("No audio frames decoded from {:?}", path))?;
+    Ok((samples, spec))
+}
+
+/// Downmixes interleaved samples for `spec` to mono.
+pub fn downmix_to_mono(samples: &[f32], spec: &SignalSpec) -> Vec<f32> {
+    let channels = spec.channels.count();
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+
+    samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
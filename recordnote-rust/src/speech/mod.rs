@@ -0,0 +1,5 @@
+mod decode;
+mod resample;
+pub mod transcriber;
+mod vad;
+mod whisper_engine;
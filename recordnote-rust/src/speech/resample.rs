@@ -0,0 +1,70 @@
+use anyhow::Result;
+use rubato::{InterpolationParameters, InterpolationType, Resampler, SincFixedIn, WindowFunction};
+
+const SINC_TAPS: usize = 256;
+const INPUT_BLOCK_SIZE: usize = 1024;
+
+/// Band-limited resampling to Whisper's expected 16 kHz mono input, using
+/// a windowed-sinc polyphase filter instead of naive sample dropping.
+pub fn resample_to_16k(samples: &[f32], from_rate: u32, to_rate: u32) -> Result<Vec<f32>> {
+    if from_rate == to_rate {
+        return Ok(samples.to_vec());
+    }
+
+    let params = InterpolationParameters {
+        sinc_len: SINC_TAPS,
+        f_cutoff: 0.95,
+        interpolation: InterpolationType::Linear,
+        oversampling_factor: 256,
+        window: WindowFunction::BlackmanHarris2,
+    };
+
+    let ratio = to_rate as f64 / from_rate as f64;
+    let mut resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, INPUT_BLOCK_SIZE, 1)?;
+
+    let mut output = Vec::with_capacity((samples.len() as f64 * ratio) as usize);
+    let mut offset = 0;
+
+    while offset + INPUT_BLOCK_SIZE <= samples.len() {
+        let block = vec![samples[offset..offset + INPUT_BLOCK_SIZE].to_vec()];
+        let resampled = resampler.process(&block, None)?;
+        output.extend_from_slice(&resampled[0]);
+        offset += INPUT_BLOCK_SIZE;
+    }
+
+    // Flush the tail: pad the final partial block so no trailing samples
+    // are lost, then trim the estimated padding back off the output.
+    if offset < samples.len() {
+        let mut tail = samples[offset..].to_vec();
+        let tail_len = tail.len();
+        tail.resize(INPUT_BLOCK_SIZE, 0.0);
+
+        let resampled = resampler.process(&[tail], None)?;
+        let expected_len = (tail_len as f64 * ratio).round() as usize;
+        output.extend_from_slice(&resampled[0][..expected_len.min(resampled[0].len())]);
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample_identity_when_rates_match() {
+        let samples = vec![0.1, 0.2, 0.3, 0.4];
+        let resampled = resample_to_16k(&samples, 16000, 16000).unwrap();
+        assert_eq!(resampled, samples);
+    }
+
+    #[test]
+    fn test_resample_changes_length_proportionally() {
+        let samples = vec![0.0f32; 44100 * 2];
+        let resampled = resample_to_16k(&samples, 44100, 16000).unwrap();
+
+        let expected_len = (samples.len() as f64 * 16000.0 / 44100.0) as usize;
+        let tolerance = 16000; // one second of 16 kHz output
+        assert!((resampled.len() as i64 - expected_len as i64).unsigned_abs() < tolerance);
+    }
+}
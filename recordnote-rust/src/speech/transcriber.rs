@@ -1,3 +1,4 @@
+use super::whisper_engine::WhisperEngine;
 use anyhow::{Context, Result};
 use hound::WavReader;
 use serde::{Deserialize, Serialize};
@@ -5,6 +6,7 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionSegment {
@@ -20,41 +22,86 @@ pub struct TranscriptionResult {
     pub segments: Vec<TranscriptionSegment>,
 }
 
+/// How aggressively `transcribe_partial` commits tokens to the stable
+/// prefix. Higher stability waits for more consecutive agreeing updates
+/// before committing, trading latency for fewer corrections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultStability {
+    Low,
+    Medium,
+    High,
+}
+
+impl ResultStability {
+    fn required_updates(&self) -> u32 {
+        match self {
+            ResultStability::Low => 1,
+            ResultStability::Medium => 3,
+            ResultStability::High => 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialToken {
+    pub text: String,
+    pub stable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialTranscription {
+    pub tokens: Vec<PartialToken>,
+    pub committed_text: String,
+    pub pending_text: String,
+}
+
 pub struct WhisperTranscriber {
     model_size: String,
-    loaded: bool,
+    language: String,
+    engine: Option<Arc<Mutex<WhisperEngine>>>,
+    stability: ResultStability,
+    previous_tokens: Vec<String>,
+    token_stability_counts: Vec<u32>,
 }
 
 impl WhisperTranscriber {
-    pub fn new(model_size: String) -> Result<Self> {        
+    pub fn new(model_size: String) -> Result<Self> {
         Ok(Self {
             model_size,
-            loaded: false,
+            language: "ja".to_string(),
+            engine: None,
+            stability: ResultStability::Medium,
+            previous_tokens: Vec::new(),
+            token_stability_counts: Vec::new(),
         })
     }
 
+    pub fn set_language(&mut self, language: String) {
+        self.language = language;
+    }
+
+    pub fn set_stability(&mut self, stability: ResultStability) {
+        self.stability = stability;
+    }
+
     pub fn default() -> Result<Self> {
         Self::new("base".to_string())
     }
 
     pub async fn load_model(&mut self) -> Result<()> {
-        if self.loaded {
+        if self.engine.is_some() {
             return Ok(()); // Already loaded
         }
 
         log::info!("Loading Whisper model: {}", self.model_size);
-        
-        // Placeholder for actual model loading
-        // In a real implementation, you would:
-        // 1. Download the Whisper model if not cached
-        // 2. Load the model weights using whisper.cpp bindings or similar
-        // 3. Initialize the tokenizer
-        
-        // Simulate loading delay
-        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-        
-        self.loaded = true;
-        
+
+        // Downloading weights and building the model is blocking work, so
+        // run it off the async task that drives the UI.
+        let model_size = self.model_size.clone();
+        let engine = tokio::task::spawn_blocking(move || WhisperEngine::load(&model_size)).await??;
+
+        self.engine = Some(Arc::new(Mutex::new(engine)));
+
         log::info!("Model loaded successfully");
         Ok(())
     }
@@ -66,60 +113,178 @@ impl WhisperTranscriber {
 
         self.load_model().await?;
 
-        // Load and preprocess audio
+        // Load and preprocess audio (resampled to 16kHz mono)
         let audio_data = self.load_audio_file(audio_path)?;
-        
-        // For now, this is a placeholder implementation
-        // In a real implementation, you would:
-        // 1. Preprocess the audio (resample to 16kHz, normalize, etc.)
-        // 2. Run the Whisper model inference
-        // 3. Decode the output tokens to text
-        // 4. Apply timestamp alignment
-        
-        // Placeholder result for Japanese text
-        let result = TranscriptionResult {
-            text: "これは音声認識の結果です。実際の実装では、Whisperモデルを使用して音声をテキストに変換します。".to_string(),
-            language: "ja".to_string(),
-            segments: vec![
-                TranscriptionSegment {
-                    start: 0.0,
-                    end: 3.0,
-                    text: "これは音声認識の結果です。".to_string(),
-                },
-                TranscriptionSegment {
-                    start: 3.0,
-                    end: 8.0,
-                    text: "実際の実装では、Whisperモデルを使用して音声をテキストに変換します。".to_string(),
-                },
-            ],
-        };
 
-        Ok(result)
+        let engine = self.engine.clone().expect("model was just loaded");
+        let language = self.language.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut engine = engine.lock().unwrap();
+            engine.transcribe(&audio_data, &language)
+        })
+        .await?
     }
 
-    pub async fn transcribe_bytes(&mut self, audio_bytes: &[u8]) -> Result<TranscriptionResult> {
+    /// Transcribes an in-memory audio buffer. `format_hint` is the file
+    /// extension (e.g. `"wav"`, `"mp3"`, `"flac"`) used so the decoder
+    /// knows which container/codec to probe for.
+    pub async fn transcribe_bytes(
+        &mut self,
+        audio_bytes: &[u8],
+        format_hint: &str,
+    ) -> Result<TranscriptionResult> {
         // Create a temporary file from bytes
-        let temp_file = tempfile::NamedTempFile::with_suffix(".wav")?;
+        let suffix = format!(".{}", format_hint.trim_start_matches('.'));
+        let temp_file = tempfile::NamedTempFile::with_suffix(&suffix)?;
         std::fs::write(temp_file.path(), audio_bytes)?;
-        
+
         self.transcribe_file(temp_file.path()).await
     }
 
+    /// Transcribes a rolling window of audio captured at `sample_rate`,
+    /// returning a hypothesis where each token is tagged `stable` once it
+    /// has remained unchanged across `self.stability`'s required number of
+    /// consecutive updates. Tokens that disagree with the previous call
+    /// reset and rejoin the unstable tail, so callers can safely replace or
+    /// discard them.
+    pub fn transcribe_partial(
+        &mut self,
+        samples: &[f32],
+        sample_rate: u32,
+    ) -> Result<PartialTranscription> {
+        if self.engine.is_none() {
+            return Err(anyhow::anyhow!("Model not loaded; call load_model() first"));
+        }
+
+        let hypothesis = self.transcribe_hypothesis(samples, sample_rate)?;
+        let tokens: Vec<String> = hypothesis
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect();
+
+        self.token_stability_counts.truncate(tokens.len());
+        while self.token_stability_counts.len() < tokens.len() {
+            self.token_stability_counts.push(0);
+        }
+
+        for (i, token) in tokens.iter().enumerate() {
+            let matches_previous = self.previous_tokens.get(i) == Some(token);
+            self.token_stability_counts[i] = if matches_previous {
+                self.token_stability_counts[i] + 1
+            } else {
+                0
+            };
+        }
+
+        self.previous_tokens = tokens.clone();
+
+        Ok(self.build_partial_result(tokens))
+    }
+
+    /// Forces every outstanding token to stable, for use when recording
+    /// stops and the unstable tail should be finalized rather than discarded.
+    pub fn finalize_partial(&mut self) -> PartialTranscription {
+        let required = self.stability.required_updates();
+        for count in self.token_stability_counts.iter_mut() {
+            *count = required;
+        }
+
+        let tokens = self.previous_tokens.clone();
+        let result = self.build_partial_result(tokens);
+
+        self.previous_tokens.clear();
+        self.token_stability_counts.clear();
+
+        result
+    }
+
+    fn build_partial_result(&self, tokens: Vec<String>) -> PartialTranscription {
+        let required = self.stability.required_updates();
+
+        let tagged: Vec<PartialToken> = tokens
+            .into_iter()
+            .enumerate()
+            .map(|(i, text)| PartialToken {
+                stable: self.token_stability_counts[i] >= required,
+                text,
+            })
+            .collect();
+
+        let committed_text = tagged
+            .iter()
+            .filter(|t| t.stable)
+            .map(|t| t.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let pending_text = tagged
+            .iter()
+            .filter(|t| !t.stable)
+            .map(|t| t.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        PartialTranscription {
+            tokens: tagged,
+            committed_text,
+            pending_text,
+        }
+    }
+
+    /// Runs the real Whisper engine over `samples` (resampling to 16kHz
+    /// first if `sample_rate` differs) and returns its text. This is a full
+    /// decode of the rolling window on every call, not true incremental
+    /// decoding, but it is genuine model output rather than canned text.
+    fn transcribe_hypothesis(&self, samples: &[f32], sample_rate: u32) -> Result<String> {
+        let resampled;
+        let pcm_16k: &[f32] = if sample_rate == 16_000 {
+            samples
+        } else {
+            resampled = super::resample::resample_to_16k(samples, sample_rate, 16_000)?;
+            &resampled
+        };
+
+        let engine = self.engine.clone().expect("checked by transcribe_partial");
+        let mut engine = engine.lock().unwrap();
+        let result = engine.transcribe(pcm_16k, &self.language)?;
+
+        Ok(result.text)
+    }
+
     pub fn get_model_info(&self) -> HashMap<String, serde_json::Value> {
         let mut info = HashMap::new();
         info.insert("model_size".to_string(), serde_json::Value::String(self.model_size.clone()));
-        info.insert("loaded".to_string(), serde_json::Value::Bool(self.loaded));
+        info.insert("loaded".to_string(), serde_json::Value::Bool(self.engine.is_some()));
         info.insert("device".to_string(), serde_json::Value::String("cpu".to_string()));
         info
     }
 
     fn load_audio_file(&self, path: &Path) -> Result<Vec<f32>> {
+        let is_wav = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.eq_ignore_ascii_case("wav"))
+            .unwrap_or(false);
+
+        let (mono_samples, sample_rate) = if is_wav {
+            self.load_wav_file(path)?
+        } else {
+            let (samples, spec) = super::decode::decode_interleaved(path)?;
+            (super::decode::downmix_to_mono(&samples, &spec), spec.rate)
+        };
+
+        if sample_rate == 16000 {
+            return Ok(mono_samples);
+        }
+
+        log::info!("Resampling audio from {} Hz to 16000 Hz", sample_rate);
+        super::resample::resample_to_16k(&mono_samples, sample_rate, 16000)
+    }
+
+    fn load_wav_file(&self, path: &Path) -> Result<(Vec<f32>, u32)> {
         let mut reader = WavReader::open(path)?;
         let spec = reader.spec();
-        
-        if spec.sample_rate != 16000 {
-            log::warn!("Audio sample rate is {}, expected 16000. Resampling may be needed.", spec.sample_rate);
-        }
 
         let samples: Result<Vec<f32>, _> = match spec.sample_format {
             hound::SampleFormat::Float => {
@@ -133,7 +298,7 @@ impl WhisperTranscriber {
         };
 
         let samples = samples?;
-        
+
         // Convert stereo to mono if needed
         let mono_samples = if spec.channels == 2 {
             samples
@@ -144,28 +309,6 @@ impl WhisperTranscriber {
             samples
         };
 
-        Ok(mono_samples)
-    }
-}
-
-// Helper function to resample audio (simplified implementation)
-fn resample_audio(samples: Vec<f32>, from_rate: u32, to_rate: u32) -> Vec<f32> {
-    if from_rate == to_rate {
-        return samples;
-    }
-    
-    // Simplified linear interpolation resampling
-    let ratio = from_rate as f64 / to_rate as f64;
-    let new_length = (samples.len() as f64 / ratio) as usize;
-    
-    let mut resampled = Vec::with_capacity(new_length);
-    
-    for i in 0..new_length {
-        let src_index = (i as f64 * ratio) as usize;
-        if src_index < samples.len() {
-            resampled.push(samples[src_index]);
-        }
+        Ok((mono_samples, spec.sample_rate))
     }
-    
-    resampled
 }
\ No newline at end of file
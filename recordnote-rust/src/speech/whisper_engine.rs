@@ -0,0 +1,288 @@
+use super::transcriber::{TranscriptionResult, TranscriptionSegment};
+use anyhow::{anyhow, Result};
+use candle_core::{Device, IndexOp, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::models::whisper::{self as whisper, audio, Config};
+use hf_hub::api::sync::Api;
+use hf_hub::{Repo, RepoType};
+use regex::Regex;
+use tokenizers::Tokenizer;
+
+/// Audio is processed in 30-second windows, the span Whisper was trained on.
+const CHUNK_SECONDS: f64 = 30.0;
+const SAMPLE_RATE: usize = 16_000;
+const MAX_DECODE_TOKENS: usize = 448;
+
+/// Dataset repo the candle Whisper examples load their precomputed mel
+/// filterbank bytes from, keyed by `{80,128}`-bin filename. There is no
+/// `candle_transformers::models::whisper::audio::load_mel_filters` - the
+/// examples embed these files locally; we fetch and cache the same bytes
+/// from the Hub instead of vendoring a binary blob into this repo.
+const MEL_FILTERS_REPO: &str = "Narsil/candle-examples";
+
+/// Loaded Whisper weights, tokenizer and config for one `model_size`,
+/// plus the machinery to run mel-spectrogram -> encoder -> decoder
+/// inference on a 16 kHz mono PCM buffer.
+pub struct WhisperEngine {
+    device: Device,
+    model: whisper::model::Whisper,
+    tokenizer: Tokenizer,
+    config: Config,
+    mel_filters: Vec<f32>,
+    /// Additive logits mask (0 or -inf per vocab id) that keeps prompt-only
+    /// special tokens from ever being greedily selected during decoding.
+    suppress_mask: Tensor,
+}
+
+impl WhisperEngine {
+    /// Downloads (and caches) the weights for `model_size` from Hugging
+    /// Face and loads them onto CPU. Blocking - callers should run this on
+    /// a blocking thread pool.
+    pub fn load(model_size: &str) -> Result<Self> {
+        let device = Device::Cpu;
+        let model_id = match model_size {
+            "tiny" => "openai/whisper-tiny",
+            "small" => "openai/whisper-small",
+            "medium" => "openai/whisper-medium",
+            _ => "openai/whisper-base",
+        };
+
+        let api = Api::new()?;
+        let repo = api.repo(Repo::new(model_id.to_string(), RepoType::Model));
+
+        let config_path = repo.get("config.json")?;
+        let tokenizer_path = repo.get("tokenizer.json")?;
+        let weights_path = repo.get("model.safetensors")?;
+
+        let config: Config = serde_json::from_str(&std::fs::read_to_string(config_path)?)?;
+        let tokenizer =
+            Tokenizer::from_file(tokenizer_path).map_err(|e| anyhow!("Failed to load tokenizer: {}", e))?;
+
+        let vb = unsafe {
+            VarBuilder::from_mmaped_safetensors(&[weights_path], whisper::DTYPE, &device)?
+        };
+        let model = whisper::model::Whisper::load(&vb, config.clone())?;
+        let mel_filters = Self::load_mel_filters(config.num_mel_bins)?;
+        let suppress_mask = Self::build_suppress_mask(&tokenizer, &device)?;
+
+        Ok(Self {
+            device,
+            model,
+            tokenizer,
+            config,
+            mel_filters,
+            suppress_mask,
+        })
+    }
+
+    /// Builds an additive logits mask that suppresses prompt-only special
+    /// tokens (language tags, task tokens, `<|notimestamps|>`, etc.) from
+    /// ever being generated, so a stray prediction among them can't corrupt
+    /// `tokens_to_segments`'s timestamp/text alternation. Timestamps are
+    /// forced on (we never append `<|notimestamps|>` to the prompt), so
+    /// timestamp tokens and `<|endoftext|>` are left unsuppressed - those
+    /// are exactly the tokens the model is expected to produce.
+    fn build_suppress_mask(tokenizer: &Tokenizer, device: &Device) -> Result<Tensor> {
+        let special_token_re = Regex::new(
+            r"^<\|(?:[a-z]{2}|startoftranscript|translate|transcribe|startoflm|startofprev|nocaptions|nospeech|notimestamps)\|>$",
+        )
+        .expect("suppress-token regex is a valid literal");
+
+        let vocab_size = tokenizer.get_vocab_size(true);
+        let mut mask = vec![0f32; vocab_size];
+        for (token, id) in tokenizer.get_vocab(true) {
+            if special_token_re.is_match(&token) {
+                mask[id as usize] = f32::NEG_INFINITY;
+            }
+        }
+
+        Ok(Tensor::from_vec(mask, vocab_size, device)?)
+    }
+
+    /// Downloads (and caches) the precomputed mel filterbank for
+    /// `num_mel_bins` and decodes it from its little-endian f32 byte
+    /// encoding, the same format/source the candle Whisper examples embed.
+    fn load_mel_filters(num_mel_bins: usize) -> Result<Vec<f32>> {
+        let filename = match num_mel_bins {
+            80 => "melfilters.bytes",
+            128 => "melfilters128.bytes",
+            n => return Err(anyhow!("unsupported num_mel_bins: {}", n)),
+        };
+
+        let api = Api::new()?;
+        let repo = api.repo(Repo::new(MEL_FILTERS_REPO.to_string(), RepoType::Dataset));
+        let path = repo.get(filename)?;
+        let bytes = std::fs::read(path)?;
+
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect())
+    }
+
+    /// Runs inference over `pcm` (16 kHz mono samples), honoring a forced
+    /// `language` (e.g. `"ja"`), and returns the full text plus one segment
+    /// per timestamp-delimited span, with absolute timestamps.
+    ///
+    /// A voice-activity pre-pass splits `pcm` into speech regions so
+    /// silence is skipped and each region is fed to the model in its own
+    /// (still Whisper-window-limited) chunks.
+    pub fn transcribe(&mut self, pcm: &[f32], language: &str) -> Result<TranscriptionResult> {
+        let chunk_len = (CHUNK_SECONDS * SAMPLE_RATE as f64) as usize;
+        let mut regions = super::vad::detect_speech_regions(pcm);
+        if regions.is_empty() {
+            regions.push((0, pcm.len()));
+        }
+
+        let mut segments = Vec::new();
+
+        for (region_start, region_end) in regions {
+            let region = &pcm[region_start..region_end];
+            let region_offset_secs = region_start as f64 / SAMPLE_RATE as f64;
+
+            let mut offset = 0;
+            while offset < region.len() {
+                let end = (offset + chunk_len).min(region.len());
+                let chunk = &region[offset..end];
+                let chunk_offset_secs = region_offset_secs + offset as f64 / SAMPLE_RATE as f64;
+
+                let mut chunk_segments = self.transcribe_chunk(chunk, language, chunk_offset_secs)?;
+                segments.append(&mut chunk_segments);
+
+                offset = end;
+            }
+        }
+
+        let text = segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join("");
+
+        Ok(TranscriptionResult {
+            text,
+            language: language.to_string(),
+            segments,
+        })
+    }
+
+    fn transcribe_chunk(
+        &mut self,
+        chunk: &[f32],
+        language: &str,
+        chunk_offset_secs: f64,
+    ) -> Result<Vec<TranscriptionSegment>> {
+        let mel = audio::pcm_to_mel(&self.config, chunk, &self.mel_filters);
+        let mel_len = mel.len() / self.config.num_mel_bins;
+        let mel = Tensor::from_vec(
+            mel,
+            (1, self.config.num_mel_bins, mel_len),
+            &self.device,
+        )?
+        .to_dtype(whisper::DTYPE)?;
+
+        let encoder_output = self.model.encoder.forward(&mel, true)?;
+
+        let sot_token = self.token_id("<|startoftranscript|>")?;
+        let language_token = self
+            .token_id(&format!("<|{}|>", language))
+            .unwrap_or(self.token_id("<|en|>")?);
+        let transcribe_token = self.token_id("<|transcribe|>")?;
+        let eot_token = self.token_id("<|endoftext|>")?;
+        let timestamp_begin = self.token_id("<|0.00|>")?;
+
+        let mut tokens = vec![sot_token, language_token, transcribe_token];
+
+        for i in 0..MAX_DECODE_TOKENS {
+            let tokens_tensor = Tensor::new(tokens.as_slice(), &self.device)?.unsqueeze(0)?;
+            let decoder_states =
+                self.model
+                    .decoder
+                    .forward(&tokens_tensor, &encoder_output, i == 0)?;
+
+            // decoder_states are hidden states (dim d_model), not vocab
+            // logits - they must go through the vocab head before argmax.
+            let (_, seq_len, _) = decoder_states.dims3()?;
+            let logits = self
+                .model
+                .decoder
+                .final_linear(&decoder_states.i((.., seq_len - 1.., ..))?)?
+                .i(0)?
+                .i(0)?;
+            let logits = logits.broadcast_add(&self.suppress_mask)?;
+
+            let logits_v: Vec<f32> = logits.to_vec1()?;
+            let next_token = logits_v
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(id, _)| id as u32)
+                .ok_or_else(|| anyhow!("decoder produced no logits"))?;
+
+            if next_token == eot_token {
+                break;
+            }
+            tokens.push(next_token);
+        }
+
+        self.tokens_to_segments(&tokens[3..], timestamp_begin, chunk_offset_secs)
+    }
+
+    /// Splits a decoded token stream into segments at timestamp-token
+    /// pairs, converting timestamp token ids to absolute seconds.
+    fn tokens_to_segments(
+        &self,
+        tokens: &[u32],
+        timestamp_begin: u32,
+        chunk_offset_secs: f64,
+    ) -> Result<Vec<TranscriptionSegment>> {
+        let mut segments = Vec::new();
+        let mut text_tokens = Vec::new();
+        let mut start_secs = 0.0;
+        let mut in_segment = false;
+
+        for &token in tokens {
+            if token >= timestamp_begin {
+                let secs = (token - timestamp_begin) as f64 * 0.02;
+                if !in_segment {
+                    start_secs = secs;
+                    in_segment = true;
+                } else {
+                    let text = self
+                        .tokenizer
+                        .decode(&text_tokens, true)
+                        .map_err(|e| anyhow!("Failed to decode tokens: {}", e))?;
+                    segments.push(TranscriptionSegment {
+                        start: chunk_offset_secs + start_secs,
+                        end: chunk_offset_secs + secs,
+                        text,
+                    });
+                    text_tokens.clear();
+                    in_segment = false;
+                }
+            } else {
+                text_tokens.push(token);
+            }
+        }
+
+        if !text_tokens.is_empty() {
+            let text = self
+                .tokenizer
+                .decode(&text_tokens, true)
+                .map_err(|e| anyhow!("Failed to decode tokens: {}", e))?;
+            segments.push(TranscriptionSegment {
+                start: chunk_offset_secs + start_secs,
+                end: chunk_offset_secs + CHUNK_SECONDS,
+                text,
+            });
+        }
+
+        Ok(segments)
+    }
+
+    fn token_id(&self, token: &str) -> Result<u32> {
+        self.tokenizer
+            .token_to_id(token)
+            .ok_or_else(|| anyhow!("Unknown special token: {}", token))
+    }
+}
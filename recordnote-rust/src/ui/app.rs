@@ -1,9 +1,15 @@
 use super::recording_widget::RecordingWidget;
-use druid::widget::{Button, Flex, Label, Scroll, Split, TextBox};
+use crate::audio::recorder::AudioRecorder;
+use druid::widget::{Button, Flex, Label, RadioGroup, Scroll, Split, TextBox};
 use druid::{
     AppLauncher, Color, Data, Env, Lens, Widget, WidgetExt, WindowDesc,
 };
 
+/// Sample rates offered in the UI regardless of which device is selected;
+/// `AudioRecorder::start_recording` will still fail gracefully if the
+/// chosen device can't actually drive the selected rate.
+const SAMPLE_RATE_OPTIONS: [u32; 4] = [16_000, 22_050, 44_100, 48_000];
+
 // Custom events for async operations
 #[derive(Debug, Clone)]
 pub enum AppEvent {
@@ -20,7 +26,10 @@ pub struct AppState {
     pub duration: String,
     pub status: String,
     pub results_text: String,
+    pub pending_text: String,
     pub model_size: String,
+    pub device_name: String,
+    pub sample_rate: u32,
     pub is_recording: bool,
     pub is_processing: bool,
     pub can_download: bool,
@@ -35,7 +44,10 @@ impl AppState {
             duration: "録音時間: 0.0秒".to_string(),
             status: "録音待機中".to_string(),
             results_text: "録音を開始して音声を議事録に変換してください。".to_string(),
+            pending_text: String::new(),
             model_size: "base".to_string(),
+            device_name: String::new(),
+            sample_rate: 16_000,
             is_recording: false,
             is_processing: false,
             can_download: false,
@@ -135,6 +147,25 @@ impl RecordNoteApp {
         .with_text_size(12.0)
         .with_text_color(Color::rgb8(117, 117, 117));
 
+        let device_label = Label::new("入力デバイス")
+            .with_text_size(14.0);
+
+        let device_selector = RadioGroup::new(self.device_options())
+            .lens(AppState::device_name)
+            .disabled_if(|data, _env| data.is_recording || data.is_processing);
+
+        let sample_rate_label = Label::new("サンプルレート")
+            .with_text_size(14.0);
+
+        let sample_rate_selector = RadioGroup::new(
+            SAMPLE_RATE_OPTIONS
+                .iter()
+                .map(|rate| (format!("{} Hz", rate), *rate))
+                .collect::<Vec<_>>(),
+        )
+        .lens(AppState::sample_rate)
+        .disabled_if(|data, _env| data.is_recording || data.is_processing);
+
         Flex::column()
             .with_child(title)
             .with_spacer(20.0)
@@ -152,10 +183,37 @@ impl RecordNoteApp {
             .with_spacer(10.0)
             .with_child(model_selector)
             .with_spacer(20.0)
+            .with_child(device_label)
+            .with_spacer(10.0)
+            .with_child(device_selector)
+            .with_spacer(20.0)
+            .with_child(sample_rate_label)
+            .with_spacer(10.0)
+            .with_child(sample_rate_selector)
+            .with_spacer(20.0)
             .padding(20.0)
             .background(Color::rgb8(248, 249, 250))
     }
 
+    /// Builds the (label, device_name) pairs for the device `RadioGroup`,
+    /// with an always-present "default" option mapping to the empty
+    /// string, which `RecordingWidget` passes to `AudioRecorder` as `None`
+    /// ("use the host default").
+    fn device_options(&self) -> Vec<(String, String)> {
+        let mut options = vec![("デフォルト".to_string(), String::new())];
+
+        match AudioRecorder::list_input_devices() {
+            Ok(devices) => {
+                options.extend(devices.into_iter().map(|d| (d.name.clone(), d.name)));
+            }
+            Err(e) => {
+                log::warn!("Failed to enumerate input devices: {}", e);
+            }
+        }
+
+        options
+    }
+
     fn build_right_panel(&self) -> impl Widget<AppState> {
         let title = Label::new("結果")
             .with_text_size(24.0)
@@ -169,6 +227,10 @@ impl RecordNoteApp {
         )
         .vertical();
 
+        let pending_label = Label::new(|data: &AppState, _env: &Env| data.pending_text.clone())
+            .with_text_size(16.0)
+            .with_text_color(Color::rgb8(158, 158, 158));
+
         let download_button = Button::new("📄 議事録をダウンロード")
             .on_click(|_ctx, data: &mut AppState, _env| {
                 if data.can_download && !data.formatted_minutes.is_empty() {
@@ -199,6 +261,7 @@ impl RecordNoteApp {
                 data.recording_state = "stopped".to_string();
                 data.status = "録音待機中".to_string();
                 data.results_text = "録音を開始して音声を議事録に変換してください。".to_string();
+                data.pending_text.clear();
                 data.meeting_title.clear();
                 data.duration = "録音時間: 0.0秒".to_string();
                 data.is_recording = false;
@@ -219,6 +282,7 @@ impl RecordNoteApp {
             .with_child(title)
             .with_spacer(20.0)
             .with_flex_child(results_area, 1.0)
+            .with_child(pending_label)
             .with_spacer(20.0)
             .with_child(button_row)
             .padding(20.0)
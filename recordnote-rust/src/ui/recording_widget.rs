@@ -1,13 +1,31 @@
 use super::app::AppState;
-use crate::audio::recorder::AudioRecorder;
+use crate::audio::recorder::{AudioRecorder, RecorderEvent};
 use crate::formatter::minutes::MinutesFormatter;
-use crate::speech::transcriber::WhisperTranscriber;
+use crate::speech::transcriber::{TranscriptionResult, WhisperTranscriber};
 use druid::{
     BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Size,
     TimerToken, UpdateCtx, Widget,
 };
 use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+
+const RECORDER_EVENT_CHANNEL_CAPACITY: usize = 32;
+const WIDGET_EVENT_CHANNEL_CAPACITY: usize = 8;
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Results of background work this widget kicks off but must not block the
+/// UI thread on: loading the Whisper model and running the final
+/// transcription once a recording is saved. Delivered to `event()` the
+/// same way `RecorderEvent`s are - drained on the next poll-timer tick.
+enum TranscriberEvent {
+    ModelLoaded(Arc<Mutex<WhisperTranscriber>>),
+    ModelLoadFailed(String),
+    TranscriptionDone(TranscriptionResult),
+    TranscriptionFailed(String),
+}
 
 pub struct RecordingWidget<W> {
     inner: W,
@@ -17,6 +35,9 @@ pub struct RecordingWidget<W> {
     timer_token: Option<TimerToken>,
     recording_started: bool,
     processing_started: bool,
+    recorder_events: Option<mpsc::Receiver<RecorderEvent>>,
+    widget_events: Option<mpsc::Receiver<TranscriberEvent>>,
+    widget_event_sender: Option<mpsc::Sender<TranscriberEvent>>,
 }
 
 impl<W> RecordingWidget<W> {
@@ -29,6 +50,9 @@ impl<W> RecordingWidget<W> {
             timer_token: None,
             recording_started: false,
             processing_started: false,
+            recorder_events: None,
+            widget_events: None,
+            widget_event_sender: None,
         }
     }
 }
@@ -40,51 +64,138 @@ impl<W: Widget<AppState>> Widget<AppState> for RecordingWidget<W> {
                 // Initialize components when window is connected
                 if self.recorder.is_none() {
                     self.recorder = Some(Arc::new(Mutex::new(AudioRecorder::default())));
+                    self.formatter = Some(MinutesFormatter::new());
+
+                    let (sender, receiver) = mpsc::channel(WIDGET_EVENT_CHANNEL_CAPACITY);
+                    self.widget_events = Some(receiver);
+                    self.widget_event_sender = Some(sender.clone());
+
                     match WhisperTranscriber::default() {
-                        Ok(transcriber) => {
-                            self.transcriber = Some(Arc::new(Mutex::new(transcriber)));
+                        Ok(mut transcriber) => {
+                            // Download/build the model on a background
+                            // thread rather than the UI thread, so the
+                            // window stays responsive; the result is
+                            // picked up from widget_events on a later
+                            // poll tick instead of being waited on here.
+                            let rt_handle = Handle::current();
+                            thread::spawn(move || {
+                                let event = match rt_handle.block_on(transcriber.load_model()) {
+                                    Ok(()) => TranscriberEvent::ModelLoaded(Arc::new(Mutex::new(
+                                        transcriber,
+                                    ))),
+                                    Err(e) => TranscriberEvent::ModelLoadFailed(e.to_string()),
+                                };
+                                let _ = sender.blocking_send(event);
+                            });
                         }
                         Err(e) => {
                             log::error!("Failed to create transcriber: {}", e);
                         }
                     }
-                    self.formatter = Some(MinutesFormatter::new());
+
+                    // Runs for the life of the widget so deferred results
+                    // (model load, final transcription) are always picked
+                    // up, not just while a recording is in progress.
+                    self.timer_token = Some(ctx.request_timer(POLL_INTERVAL));
                     log::info!("Audio components initialized");
                 }
             }
             Event::Timer(token) => {
-                if let Some(timer_token) = &self.timer_token {
-                    if token == timer_token {
-                        // Update duration if recording
-                        if let Some(recorder) = &self.recorder {
-                            if let Ok(recorder) = recorder.try_lock() {
-                                if recorder.is_recording() {
-                                    let duration = recorder.get_duration();
-                                    data.duration = format!("録音時間: {:.1}秒", duration);
-                                    ctx.request_update();
-                                    
-                                    // Schedule next update
-                                    self.timer_token = Some(ctx.request_timer(Duration::from_millis(100)));
+                let is_poll_tick = self.timer_token.as_ref() == Some(token);
+                if is_poll_tick {
+                    self.timer_token = Some(ctx.request_timer(POLL_INTERVAL));
+
+                    if let Some(events) = &mut self.widget_events {
+                        while let Ok(event) = events.try_recv() {
+                            match event {
+                                TranscriberEvent::ModelLoaded(transcriber) => {
+                                    self.transcriber = Some(transcriber);
+                                    log::info!("Model loaded successfully");
+                                }
+                                TranscriberEvent::ModelLoadFailed(e) => {
+                                    log::error!("Failed to load transcriber model: {}", e);
+                                }
+                                TranscriberEvent::TranscriptionDone(result) => {
+                                    if let Some(formatter) = &self.formatter {
+                                        let formatted = formatter.format_minutes(&result, None);
+                                        log::info!(
+                                            "Processing completed: {} chars",
+                                            formatted.len()
+                                        );
+                                        data.results_text = formatted.clone();
+                                        data.formatted_minutes = formatted;
+                                        data.can_download = true;
+                                        data.status = "✅ 処理完了!".to_string();
+                                    }
+                                    data.is_processing = false;
+                                    data.recording_state = "completed".to_string();
+                                }
+                                TranscriberEvent::TranscriptionFailed(e) => {
+                                    log::error!("Transcription failed: {}", e);
+                                    data.status = "❌ 文字起こしに失敗しました".to_string();
+                                    data.is_processing = false;
+                                    data.recording_state = "stopped".to_string();
+                                }
+                            }
+                        }
+                    }
+
+                    // Update duration and drain any partial-transcription
+                    // events the recorder's consumer thread produced
+                    // since the last tick.
+                    if let Some(recorder) = &self.recorder {
+                        if let Ok(recorder) = recorder.try_lock() {
+                            if recorder.is_recording() {
+                                let duration = recorder.get_duration();
+                                data.duration = format!("録音時間: {:.1}秒", duration);
+
+                                if let Some(receiver) = &mut self.recorder_events {
+                                    while let Ok(event) = receiver.try_recv() {
+                                        match event {
+                                            RecorderEvent::TranscriptionPartial {
+                                                committed,
+                                                pending,
+                                            } => {
+                                                data.results_text = committed;
+                                                data.pending_text = pending;
+                                            }
+                                            RecorderEvent::TranscriptionError(e) => {
+                                                log::warn!("Partial transcription failed: {}", e);
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
                     }
+
+                    ctx.request_update();
                 }
             }
             _ => {}
         }
 
         // Handle state changes
-        if let (Some(recorder), Some(transcriber), Some(formatter)) = 
+        if let (Some(recorder), Some(transcriber), Some(formatter)) =
             (&self.recorder, &self.transcriber, &self.formatter) {
-            
+
             // Start recording if requested and not already started
             if data.is_recording && !self.recording_started {
                 if let Ok(mut recorder_guard) = recorder.try_lock() {
-                    match recorder_guard.start_recording() {
+                    let device_name = if data.device_name.is_empty() {
+                        None
+                    } else {
+                        Some(data.device_name.clone())
+                    };
+                    recorder_guard.set_device(device_name);
+                    recorder_guard.set_sample_rate(data.sample_rate);
+
+                    let (event_sender, event_receiver) =
+                        mpsc::channel(RECORDER_EVENT_CHANNEL_CAPACITY);
+                    match recorder_guard.start_recording(Arc::clone(transcriber), event_sender) {
                         Ok(()) => {
                             self.recording_started = true;
-                            self.timer_token = Some(ctx.request_timer(Duration::from_millis(100)));
+                            self.recorder_events = Some(event_receiver);
                             log::info!("Recording started");
                         }
                         Err(e) => {
@@ -100,41 +211,72 @@ impl<W: Widget<AppState>> Widget<AppState> for RecordingWidget<W> {
             if data.is_processing && !self.processing_started && self.recording_started {
                 self.processing_started = true;
                 self.recording_started = false;
-                
+
                 // Stop recording synchronously
                 if let Ok(mut recorder_guard) = recorder.try_lock() {
                     match recorder_guard.stop_recording() {
-                        Ok(()) => {
+                        Ok(outcome) => {
                             log::info!("Recording stopped, processing...");
-                            
-                            // For demo purposes, create dummy transcription result
-                            let dummy_result = crate::speech::transcriber::TranscriptionResult {
-                                text: "これは音声認識の結果です。実際の録音内容がここに表示されます。".to_string(),
-                                language: "ja".to_string(),
-                                segments: vec![
-                                    crate::speech::transcriber::TranscriptionSegment {
-                                        start: 0.0,
-                                        end: 3.0,
-                                        text: "これは音声認識の結果です。".to_string(),
-                                    },
-                                    crate::speech::transcriber::TranscriptionSegment {
-                                        start: 3.0,
-                                        end: 6.0,
-                                        text: "実際の録音内容がここに表示されます。".to_string(),
-                                    },
-                                ],
-                            };
-                            
-                            let formatted = formatter.format_minutes(&dummy_result, None);
-                            log::info!("Processing completed: {} chars", formatted.len());
-                            
-                            // Update UI with results
-                            data.results_text = formatted.clone();
-                            data.formatted_minutes = formatted;
-                            data.is_processing = false;
-                            data.can_download = true;
-                            data.recording_state = "completed".to_string();
-                            data.status = "✅ 処理完了!".to_string();
+
+                            // Commit whatever was still in the unstable tail
+                            // now that recording has ended.
+                            if let Ok(mut transcriber_guard) = transcriber.try_lock() {
+                                transcriber_guard.finalize_partial();
+                            }
+                            data.pending_text.clear();
+
+                            match outcome {
+                                crate::audio::recorder::RecordingOutcome::Saved(path) => {
+                                    log::info!("Recording saved to {:?}", path);
+
+                                    // Runs the decode on a background
+                                    // thread so stop/process never
+                                    // freezes the window; the result
+                                    // arrives via widget_events on a
+                                    // later poll tick.
+                                    if let Some(sender) = self.widget_event_sender.clone() {
+                                        let transcriber = Arc::clone(transcriber);
+                                        let rt_handle = Handle::current();
+                                        thread::spawn(move || {
+                                            let event = match transcriber.lock() {
+                                                Ok(mut guard) => {
+                                                    match rt_handle
+                                                        .block_on(guard.transcribe_file(&path))
+                                                    {
+                                                        Ok(result) => {
+                                                            TranscriberEvent::TranscriptionDone(
+                                                                result,
+                                                            )
+                                                        }
+                                                        Err(e) => {
+                                                            TranscriberEvent::TranscriptionFailed(
+                                                                e.to_string(),
+                                                            )
+                                                        }
+                                                    }
+                                                }
+                                                Err(_) => TranscriberEvent::TranscriptionFailed(
+                                                    "transcriber lock poisoned".to_string(),
+                                                ),
+                                            };
+                                            let _ = sender.blocking_send(event);
+                                        });
+                                    } else {
+                                        data.status =
+                                            "❌ 文字起こしに失敗しました".to_string();
+                                        data.is_processing = false;
+                                        data.recording_state = "stopped".to_string();
+                                    }
+                                }
+                                crate::audio::recorder::RecordingOutcome::Empty => {
+                                    log::warn!("No audio captured; nothing saved");
+                                    data.results_text =
+                                        "録音された音声がありませんでした。".to_string();
+                                    data.status = "⚠️ 録音された音声がありません".to_string();
+                                    data.is_processing = false;
+                                    data.recording_state = "completed".to_string();
+                                }
+                            }
                         }
                         Err(e) => {
                             log::error!("Failed to stop recording: {}", e);
@@ -150,7 +292,7 @@ impl<W: Widget<AppState>> Widget<AppState> for RecordingWidget<W> {
             if !data.is_recording && !data.is_processing && self.recording_started {
                 self.recording_started = false;
                 self.processing_started = false;
-                self.timer_token = None;
+                self.recorder_events = None;
             }
         }
 
@@ -172,4 +314,4 @@ impl<W: Widget<AppState>> Widget<AppState> for RecordingWidget<W> {
     fn paint(&mut self, ctx: &mut PaintCtx, data: &AppState, env: &Env) {
         self.inner.paint(ctx, data, env);
     }
-}
\ No newline at end of file
+}
@@ -104,7 +104,34 @@ fn test_recorder_state_management() {
     assert!(!recorder.is_recording());
 }
 
-#[tokio::test] 
+#[tokio::test]
+#[ignore = "downloads Whisper model weights from Hugging Face"]
+async fn test_transcribe_partial_stabilizes_tokens() {
+    use recordnote::speech::transcriber::ResultStability;
+
+    let mut transcriber = WhisperTranscriber::new("tiny".to_string()).unwrap();
+    transcriber.load_model().await.unwrap();
+    transcriber.set_stability(ResultStability::Low);
+
+    // A steady tone rather than silence, so the real Whisper engine has
+    // something to latch onto and produce non-empty (if nonsensical) text.
+    let samples: Vec<f32> = (0..16_000 * 2)
+        .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 16_000.0).sin())
+        .collect();
+    let first = transcriber.transcribe_partial(&samples, 16_000).unwrap();
+    assert!(!first.tokens.is_empty());
+
+    // Feeding the same window again should stabilize tokens that repeat,
+    // since the same audio decodes to the same text both times.
+    let second = transcriber.transcribe_partial(&samples, 16_000).unwrap();
+    assert!(second.tokens.iter().any(|t| t.stable));
+
+    let finalized = transcriber.finalize_partial();
+    assert!(finalized.tokens.iter().all(|t| t.stable));
+    assert!(finalized.pending_text.is_empty());
+}
+
+#[tokio::test]
 async fn test_transcriber_model_info() {
     let mut transcriber = WhisperTranscriber::new("tiny".to_string()).unwrap();
     let info = transcriber.get_model_info();